@@ -0,0 +1,303 @@
+use anyhow::Error;
+
+use super::model::{ColorTempSpace, Device, DynamicScene, Scenes};
+use super::wiz_errors::DeviceColorModeError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RGBColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl RGBColor {
+    pub fn new(red: u8, green: u8, blue: u8) -> Self {
+        RGBColor { red, green, blue }
+    }
+
+    // McCamy's polynomial approximation over the chromaticity implied by this RGB
+    // triplet. Not the inverse of `from_color_temp` (a different, independently-fit
+    // approximation) - round-tripping a temperature through both can drift by
+    // 1000K+ near the ends of WiZ's 2200-6500K range.
+    pub fn to_color_temp(self) -> u16 {
+        let red = self.red as f64 / 255.0;
+        let green = self.green as f64 / 255.0;
+        let blue = self.blue as f64 / 255.0;
+
+        let x = 0.4124 * red + 0.3576 * green + 0.1805 * blue;
+        let y = 0.2126 * red + 0.7152 * green + 0.0722 * blue;
+        let z = 0.0193 * red + 0.1192 * green + 0.9505 * blue;
+
+        let sum = x + y + z;
+        if sum == 0.0 {
+            return 0;
+        }
+
+        let chromaticity_x = x / sum;
+        let chromaticity_y = y / sum;
+        let n = (chromaticity_x - 0.3320) / (0.1858 - chromaticity_y);
+        let cct = 437.0 * n.powi(3) + 3601.0 * n.powi(2) + 6831.0 * n + 5517.0;
+
+        cct.clamp(1000.0, 40000.0) as u16
+    }
+
+    // Tanner Helland's blackbody-radiation approximation. See the note on
+    // `to_color_temp` - this does not invert it.
+    pub fn from_color_temp(kelvin: u16) -> Self {
+        let temp = kelvin as f64 / 100.0;
+
+        let red = if temp <= 66.0 {
+            255.0
+        } else {
+            (329.698_727_446 * (temp - 60.0).powf(-0.133_204_759_2)).clamp(0.0, 255.0)
+        };
+
+        let green = if temp <= 66.0 {
+            (99.470_802_586_1 * temp.ln() - 161.119_568_166_1).clamp(0.0, 255.0)
+        } else {
+            (288.122_169_528_3 * (temp - 60.0).powf(-0.075_514_849_2)).clamp(0.0, 255.0)
+        };
+
+        let blue = if temp >= 66.0 {
+            255.0
+        } else if temp <= 19.0 {
+            0.0
+        } else {
+            (138.517_731_223_1 * (temp - 10.0).ln() - 305.044_792_730_7).clamp(0.0, 255.0)
+        };
+
+        RGBColor::new(red as u8, green as u8, blue as u8)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WhiteChannels {
+    pub warm_white: u8,
+    pub cool_white: u8,
+}
+
+impl WhiteChannels {
+    pub fn new(warm_white: u8, cool_white: u8) -> Self {
+        WhiteChannels {
+            warm_white,
+            cool_white,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ColorMode {
+    Rgb(RGBColor),
+    ColorTemp(u16),
+    White(WhiteChannels),
+    Scene(Scenes, Option<u8>),
+}
+
+impl ColorTempSpace {
+    pub fn to_rgb(self, temp: u16) -> RGBColor {
+        RGBColor::from_color_temp(self.clamp(temp))
+    }
+
+    pub fn from_rgb(self, color: RGBColor) -> u16 {
+        self.clamp(color.to_color_temp())
+    }
+}
+
+impl Device {
+    pub fn resolve_color(self: &Self, mode: ColorMode) -> Result<ColorMode, Error> {
+        let definition = self.get_definition();
+        let features = definition.features;
+
+        match mode {
+            ColorMode::Rgb(color) => {
+                if features.hue {
+                    Ok(ColorMode::Rgb(color))
+                } else if let Some(color_temp) = definition.descriptor.color_temp {
+                    Ok(ColorMode::ColorTemp(color_temp.from_rgb(color)))
+                } else {
+                    Err(Error::new(DeviceColorModeError {
+                        data: definition.descriptor,
+                        details: "Device supports neither hue nor color temp.".to_string(),
+                    }))
+                }
+            }
+            ColorMode::ColorTemp(temp) => {
+                if let Some(color_temp) = definition.descriptor.color_temp {
+                    Ok(ColorMode::ColorTemp(color_temp.clamp(temp)))
+                } else if features.hue {
+                    Ok(ColorMode::Rgb(RGBColor::from_color_temp(temp)))
+                } else {
+                    Err(Error::new(DeviceColorModeError {
+                        data: definition.descriptor,
+                        details: "Device has no color temp space to clamp into.".to_string(),
+                    }))
+                }
+            }
+            ColorMode::White(channels) => {
+                if !features.dimming {
+                    return Err(Error::new(DeviceColorModeError {
+                        data: definition.descriptor,
+                        details: "Device does not support white channels.".to_string(),
+                    }));
+                }
+
+                let white_channels = definition.descriptor.white_channels.unwrap_or(1);
+                if white_channels < 2 && channels.cool_white != 0 {
+                    return Err(Error::new(DeviceColorModeError {
+                        data: definition.descriptor,
+                        details: "Device only has a single white channel and cannot set cool_white separately.".to_string(),
+                    }));
+                }
+
+                Ok(ColorMode::White(channels))
+            }
+            ColorMode::Scene(scene, speed) => {
+                if !self.supports_scene(scene) {
+                    return Err(Error::new(DeviceColorModeError {
+                        data: definition.descriptor,
+                        details: "Device does not support this scene.".to_string(),
+                    }));
+                }
+
+                match speed {
+                    Some(speed) => {
+                        let dynamic: DynamicScene = scene.with_speed(speed)?;
+                        Ok(ColorMode::Scene(dynamic.scene(), Some(dynamic.speed())))
+                    }
+                    None => Ok(ColorMode::Scene(scene, None)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::model::{DeviceDescriptor, DeviceFeatures, DeviceType};
+    use super::*;
+
+    fn device_with(features: DeviceFeatures, color_temp: Option<ColorTempSpace>) -> Device {
+        Device::new(
+            DeviceType::BulbDW,
+            Some(features),
+            Some(DeviceDescriptor {
+                module_name: None,
+                color_temp,
+                firmware_version: None,
+                white_channels: None,
+                white_to_color_ratio: None,
+                type_id_index: None,
+            }),
+        )
+    }
+
+    fn features(hue: bool, color_temp: bool, dimming: bool) -> DeviceFeatures {
+        DeviceFeatures {
+            hue,
+            color_temp,
+            effects: true,
+            dimming,
+            dual_head: false,
+        }
+    }
+
+    #[test]
+    fn rgb_falls_back_to_color_temp_when_device_lacks_hue() {
+        let device = device_with(
+            features(false, true, true),
+            Some(ColorTempSpace {
+                min_temp: 2200,
+                max_temp: 6500,
+            }),
+        );
+
+        let resolved = device
+            .resolve_color(ColorMode::Rgb(RGBColor::new(255, 255, 255)))
+            .unwrap();
+
+        assert!(matches!(resolved, ColorMode::ColorTemp(_)));
+    }
+
+    #[test]
+    fn rgb_errors_when_device_supports_neither_hue_nor_color_temp() {
+        let device = device_with(features(false, false, true), None);
+
+        let resolved = device.resolve_color(ColorMode::Rgb(RGBColor::new(255, 255, 255)));
+
+        assert!(resolved.is_err());
+    }
+
+    #[test]
+    fn color_temp_falls_back_to_rgb_when_device_lacks_color_temp_space() {
+        let device = device_with(features(true, false, true), None);
+
+        let resolved = device.resolve_color(ColorMode::ColorTemp(2700)).unwrap();
+
+        assert!(matches!(resolved, ColorMode::Rgb(_)));
+    }
+
+    #[test]
+    fn color_temp_clamps_into_device_space() {
+        let device = device_with(
+            features(false, true, true),
+            Some(ColorTempSpace {
+                min_temp: 2200,
+                max_temp: 6500,
+            }),
+        );
+
+        let resolved = device.resolve_color(ColorMode::ColorTemp(10000)).unwrap();
+
+        assert!(matches!(resolved, ColorMode::ColorTemp(6500)));
+    }
+
+    #[test]
+    fn white_is_rejected_without_dimming() {
+        let device = device_with(features(false, false, false), None);
+
+        let resolved = device.resolve_color(ColorMode::White(WhiteChannels::new(255, 0)));
+
+        assert!(resolved.is_err());
+    }
+
+    #[test]
+    fn white_rejects_cool_white_on_a_single_channel_device() {
+        let device = device_with(features(false, false, true), None);
+
+        let resolved = device.resolve_color(ColorMode::White(WhiteChannels::new(255, 10)));
+
+        assert!(resolved.is_err());
+    }
+
+    #[test]
+    fn white_accepts_warm_only_on_a_single_channel_device() {
+        let device = device_with(features(false, false, true), None);
+
+        let resolved = device.resolve_color(ColorMode::White(WhiteChannels::new(255, 0)));
+
+        assert!(resolved.is_ok());
+    }
+
+    #[test]
+    fn scene_is_rejected_when_device_does_not_support_it() {
+        let device = device_with(features(false, false, false), None);
+
+        let resolved = device.resolve_color(ColorMode::Scene(Scenes::Fireplace, None));
+
+        assert!(resolved.is_err());
+    }
+
+    #[test]
+    fn scene_with_speed_is_clamped_via_with_speed() {
+        let device = device_with(features(true, true, true), None);
+
+        let resolved = device
+            .resolve_color(ColorMode::Scene(Scenes::Fireplace, Some(255)))
+            .unwrap();
+
+        assert!(matches!(
+            resolved,
+            ColorMode::Scene(Scenes::Fireplace, Some(speed)) if speed == DynamicScene::MAX_SPEED
+        ));
+    }
+}
@@ -1,11 +1,12 @@
 use super::wiz_errors::{
-    DeviceColorTempParseError, DeviceTypeParseError, SceneIDError, SceneNameError,
+    DeviceColorTempParseError, DeviceTypeParseError, SceneIDError, SceneNameError, SceneSpeedError,
 };
 use anyhow::Error;
 use lazy_static::lazy_static;
-use num::FromPrimitive;
+use num::{FromPrimitive, ToPrimitive};
 use num_derive::{FromPrimitive, ToPrimitive};
 use optional_struct::OptionalStruct;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 
 lazy_static! {
@@ -52,7 +53,70 @@ lazy_static! {
         single_head: "SH",
         socket: "SOCKET"
     };
-    static ref KNOWN_TYPE_IDS: Vec<DeviceType> = vec![DeviceType::BulbDW];
+    static ref TYPE_ID_REGISTRY: Vec<DeviceTypeRegistryEntry> = vec![
+        DeviceTypeRegistryEntry {
+            module_name: "ESP01_SHDW_01",
+            device_type: DeviceType::BulbDW,
+            features: DeviceFeatures {
+                hue: false,
+                color_temp: false,
+                effects: true,
+                dimming: true,
+                dual_head: false,
+            },
+            color_temp: None,
+            white_channels: Some(1),
+            white_to_color_ratio: None,
+        },
+        DeviceTypeRegistryEntry {
+            module_name: "ESP06_SHTW1C_01",
+            device_type: DeviceType::BulbTW,
+            features: DeviceFeatures {
+                hue: false,
+                color_temp: true,
+                effects: true,
+                dimming: true,
+                dual_head: false,
+            },
+            color_temp: Some(ColorTempSpace {
+                min_temp: 2200,
+                max_temp: 6500,
+            }),
+            white_channels: Some(2),
+            white_to_color_ratio: Some(1),
+        },
+        DeviceTypeRegistryEntry {
+            module_name: "ESP03_SHRGB1C_01",
+            device_type: DeviceType::BulbRGB,
+            features: DeviceFeatures {
+                hue: true,
+                color_temp: true,
+                effects: true,
+                dimming: true,
+                dual_head: false,
+            },
+            color_temp: Some(ColorTempSpace {
+                min_temp: 2200,
+                max_temp: 6500,
+            }),
+            white_channels: Some(2),
+            white_to_color_ratio: Some(20),
+        },
+        DeviceTypeRegistryEntry {
+            module_name: "ESP10_SOCKET_01",
+            device_type: DeviceType::Socket,
+            features: DeviceFeatures {
+                hue: false,
+                color_temp: false,
+                effects: false,
+                dimming: false,
+                dual_head: false,
+            },
+            color_temp: None,
+            white_channels: None,
+            white_to_color_ratio: None,
+        },
+    ];
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -65,7 +129,7 @@ pub struct DeviceOptions {
     socket: &'static str,
 }
 
-#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Scenes {
     Ocean = 1,
     Romance = 2,
@@ -141,15 +205,114 @@ impl Scenes {
         let dimmable_white_scenes = vec![9, 10, 13, 14, 29, 30, 31, 32];
         return Scenes::get_scenes_list(dimmable_white_scenes);
     }
+
+    pub fn get_all_scenes() -> Result<Vec<Self>, Error> {
+        let mut all_scenes: Vec<u32> = (1..=32).collect();
+        all_scenes.push(1000);
+        return Scenes::get_scenes_list(all_scenes);
+    }
+
+    pub fn is_dynamic(self) -> bool {
+        !matches!(
+            self,
+            Scenes::WarmWhite
+                | Scenes::Daylight
+                | Scenes::CoolWhite
+                | Scenes::NighLight
+                | Scenes::Focus
+                | Scenes::Relax
+                | Scenes::Truecolors
+                | Scenes::TVtime
+        )
+    }
+
+    pub fn mode(self) -> SceneMode {
+        if self.is_dynamic() {
+            SceneMode::Dynamic
+        } else {
+            SceneMode::Static
+        }
+    }
+
+    pub fn with_speed(self, speed: u8) -> Result<DynamicScene, Error> {
+        if !self.is_dynamic() {
+            return Err(Error::new(SceneSpeedError {
+                scene: self,
+                given_speed: speed,
+                details: "Scene is static and does not support an effect speed.".into(),
+            }));
+        }
+
+        Ok(DynamicScene {
+            scene: self,
+            speed: speed.clamp(DynamicScene::MIN_SPEED, DynamicScene::MAX_SPEED),
+        })
+    }
+}
+
+impl Serialize for Scenes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ToPrimitive::to_u32(self)
+            .unwrap_or_default()
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scenes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = u32::deserialize(deserializer)?;
+        Scenes::from_id(id).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneMode {
+    Static,
+    Dynamic,
 }
 
 #[derive(Debug, Clone, Copy)]
+pub struct DynamicScene {
+    scene: Scenes,
+    speed: u8,
+}
+
+impl DynamicScene {
+    pub const MIN_SPEED: u8 = 10;
+    pub const MAX_SPEED: u8 = 200;
+
+    pub fn scene(&self) -> Scenes {
+        self.scene
+    }
+
+    pub fn speed(&self) -> u8 {
+        self.speed
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct ColorTempSpace {
     pub min_temp: u16,
     pub max_temp: u16,
 }
 
-#[derive(Debug, Clone, Copy, OptionalStruct)]
+impl ColorTempSpace {
+    pub fn clamp(self, temp: u16) -> u16 {
+        temp.clamp(self.min_temp, self.max_temp)
+    }
+
+    pub fn contains(self, temp: u16) -> bool {
+        temp >= self.min_temp && temp <= self.max_temp
+    }
+}
+
+#[derive(Debug, Clone, Copy, OptionalStruct, Serialize, Deserialize)]
 #[optional_derive(Debug, Clone, Copy)]
 pub struct DeviceFeatures {
     pub hue: bool,
@@ -159,7 +322,7 @@ pub struct DeviceFeatures {
     pub dual_head: bool,
 }
 
-#[derive(Debug, Clone, OptionalStruct)]
+#[derive(Debug, Clone, OptionalStruct, Serialize, Deserialize)]
 #[optional_derive(Debug, Clone)]
 pub struct DeviceDescriptor {
     pub module_name: Option<String>,
@@ -184,6 +347,75 @@ pub enum DeviceType {
     Socket,
 }
 
+#[derive(Debug, Clone)]
+pub struct DeviceTypeRegistryEntry {
+    pub module_name: &'static str,
+    pub device_type: DeviceType,
+    pub features: DeviceFeatures,
+    pub color_temp: Option<ColorTempSpace>,
+    pub white_channels: Option<u16>,
+    pub white_to_color_ratio: Option<u16>,
+}
+
+impl DeviceTypeRegistryEntry {
+    fn definition(&self) -> DeviceDefinition {
+        DeviceDefinition {
+            features: self.features,
+            descriptor: DeviceDescriptor {
+                module_name: Some(self.module_name.to_string()),
+                color_temp: self.color_temp,
+                firmware_version: None,
+                white_channels: self.white_channels,
+                white_to_color_ratio: self.white_to_color_ratio,
+                type_id_index: None,
+            },
+        }
+    }
+}
+
+impl DeviceType {
+    fn identifier_from_module_name(name: &str) -> Result<&str, Error> {
+        name.split('_')
+            .collect::<Vec<&str>>()
+            .get(1)
+            .copied()
+            .ok_or(Error::new(DeviceTypeParseError {
+                data: DeviceDescriptor {
+                    module_name: Some(name.to_string()),
+                    color_temp: None,
+                    firmware_version: None,
+                    white_channels: None,
+                    white_to_color_ratio: None,
+                    type_id_index: None,
+                },
+                details: "Failed to find an identifier in the module name.".to_string(),
+            }))
+    }
+
+    fn from_identifier(identifier: &str) -> Self {
+        if identifier.contains(DEVICE_OPTS.rgb) {
+            DeviceType::BulbRGB
+        } else if identifier.contains(DEVICE_OPTS.tunable_white) {
+            DeviceType::BulbTW
+        } else if identifier.contains(DEVICE_OPTS.socket) {
+            DeviceType::Socket
+        } else {
+            DeviceType::BulbDW
+        }
+    }
+
+    pub fn from_module_name(name: &str) -> Result<Self, Error> {
+        let identifier = Self::identifier_from_module_name(name)?;
+        Ok(Self::from_identifier(identifier))
+    }
+
+    pub fn from_type_id_index(type_id_index: usize) -> Option<(Self, DeviceDefinition)> {
+        TYPE_ID_REGISTRY
+            .get(type_id_index)
+            .map(|entry| (entry.device_type.clone(), entry.definition()))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Device {
     Bulb(Bulb),
@@ -312,52 +544,55 @@ impl Device {
         let descriptor_bind = descriptor.clone();
 
         if let Some(name) = descriptor.module_name {
-            let identifier = name
-                .split("_")
-                .collect::<Vec<&str>>()
-                .get(1)
-                .ok_or(Error::new(DeviceTypeParseError {
-                    data: descriptor_bind.clone(),
-                    details: "Failed to find an identifier in the descriptor.".to_string(),
-                }))?
-                .clone();
-
-            if identifier.contains(DEVICE_OPTS.rgb) {
-                device = Device::new(DeviceType::BulbRGB, None, None);
-            } else if identifier.contains(DEVICE_OPTS.tunable_white) {
-                device = Device::new(DeviceType::BulbTW, None, None);
-            } else if identifier.contains(DEVICE_OPTS.socket) {
-                device = Device::new(DeviceType::Socket, None, None);
+            if let Some(entry) = TYPE_ID_REGISTRY
+                .iter()
+                .find(|entry| entry.module_name == name)
+            {
+                let definition = entry.definition();
+                device = Device::new(
+                    entry.device_type.clone(),
+                    Some(definition.features),
+                    Some(definition.descriptor),
+                );
             } else {
-                let effects = identifier.contains(DEVICE_OPTS.dual_head)
-                    || identifier.contains(DEVICE_OPTS.single_head);
-                let dual_head = identifier.contains(DEVICE_OPTS.dual_head);
-                let patch = OptionalDeviceFeatures {
-                    hue: None,
-                    color_temp: None,
-                    effects: Some(effects),
-                    dimming: None,
-                    dual_head: Some(dual_head),
-                };
-                device = device.patch_features(patch);
+                let identifier = DeviceType::identifier_from_module_name(&name).map_err(|_| {
+                    Error::new(DeviceTypeParseError {
+                        data: descriptor_bind.clone(),
+                        details: "Failed to find an identifier in the descriptor.".to_string(),
+                    })
+                })?;
+                let device_type = DeviceType::from_identifier(identifier);
+                device = Device::new(device_type, None, None);
+
+                if !identifier.contains(DEVICE_OPTS.rgb)
+                    && !identifier.contains(DEVICE_OPTS.tunable_white)
+                    && !identifier.contains(DEVICE_OPTS.socket)
+                {
+                    let effects = identifier.contains(DEVICE_OPTS.dual_head)
+                        || identifier.contains(DEVICE_OPTS.single_head);
+                    let dual_head = identifier.contains(DEVICE_OPTS.dual_head);
+                    let patch = OptionalDeviceFeatures {
+                        hue: None,
+                        color_temp: None,
+                        effects: Some(effects),
+                        dimming: None,
+                        dual_head: Some(dual_head),
+                    };
+                    device = device.patch_features(patch);
+                }
             }
         } else if let Some(type_id_index) = descriptor.type_id_index {
-            let device_type = KNOWN_TYPE_IDS
-                .get(type_id_index)
-                .ok_or(Error::new(DeviceTypeParseError {
+            let (device_type, definition) = DeviceType::from_type_id_index(type_id_index).ok_or(
+                Error::new(DeviceTypeParseError {
                     data: descriptor,
-                    details: "Failed finding a known type ID in the descriptor".to_string(),
-                }))?
-                .clone();
-            device = Device::new(device_type, None, None);
-            let patch = OptionalDeviceFeatures {
-                hue: None,
-                color_temp: None,
-                effects: Some(true),
-                dimming: None,
-                dual_head: None,
-            };
-            device = device.patch_features(patch);
+                    details: "Failed finding a known type ID in the registry".to_string(),
+                }),
+            )?;
+            device = Device::new(
+                device_type,
+                Some(definition.features),
+                Some(definition.descriptor),
+            );
         }
 
         if let Some(color_temp) = descriptor_bind.color_temp {
@@ -371,8 +606,10 @@ impl Device {
             };
 
             device = device.patch_descriptor(descriptor);
-        } else if device.get_type() == DeviceType::BulbRGB
-            || device.get_type() == DeviceType::BulbTW
+        }
+
+        if device.get_definition().descriptor.color_temp.is_none()
+            && (device.get_type() == DeviceType::BulbRGB || device.get_type() == DeviceType::BulbTW)
         {
             return Err(Error::new(DeviceColorTempParseError {
                 data: device.get_definition().descriptor,
@@ -428,4 +665,65 @@ impl Device {
             },
         }
     }
+
+    pub fn available_scenes(self: &Self) -> Vec<Scenes> {
+        let features = self.get_definition().features;
+
+        if !features.effects {
+            return Vec::new();
+        }
+
+        if features.hue {
+            Scenes::get_all_scenes().unwrap_or_default()
+        } else if features.color_temp {
+            Scenes::get_tunable_white_scenes().unwrap_or_default()
+        } else if features.dimming {
+            Scenes::get_dimmable_white_scenes().unwrap_or_default()
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn supports_scene(self: &Self, scene: Scenes) -> bool {
+        self.available_scenes().contains(&scene)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_descriptor_accepts_registry_color_temp_defaults() {
+        let descriptor = DeviceDescriptor {
+            module_name: Some("ESP06_SHTW1C_01".to_string()),
+            color_temp: None,
+            firmware_version: None,
+            white_channels: None,
+            white_to_color_ratio: None,
+            type_id_index: None,
+        };
+
+        let device = Device::from_descriptor(descriptor).unwrap();
+
+        assert_eq!(device.get_type(), DeviceType::BulbTW);
+        assert!(device.get_definition().descriptor.color_temp.is_some());
+    }
+
+    #[test]
+    fn with_speed_clamps_to_the_valid_range() {
+        let too_slow = Scenes::Fireplace.with_speed(0).unwrap();
+        assert_eq!(too_slow.speed(), DynamicScene::MIN_SPEED);
+
+        let too_fast = Scenes::Fireplace.with_speed(255).unwrap();
+        assert_eq!(too_fast.speed(), DynamicScene::MAX_SPEED);
+
+        let in_range = Scenes::Fireplace.with_speed(100).unwrap();
+        assert_eq!(in_range.speed(), 100);
+    }
+
+    #[test]
+    fn with_speed_rejects_static_scenes() {
+        assert!(Scenes::WarmWhite.with_speed(100).is_err());
+    }
 }
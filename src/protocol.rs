@@ -0,0 +1,133 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::color::ColorMode;
+use super::model::{ColorTempSpace, DeviceDescriptor, Scenes};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemConfig {
+    #[serde(rename = "moduleName")]
+    pub module_name: String,
+    #[serde(rename = "fwVersion")]
+    pub firmware_version: String,
+    #[serde(rename = "extRange", default, skip_serializing_if = "Option::is_none")]
+    pub ext_range: Option<[u16; 2]>,
+    #[serde(rename = "groupId", default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pilot {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<bool>,
+    // WiZ reports `sceneId: 0` for "no scene running" (manual color/temp mode),
+    // which isn't a valid Scenes variant - map it to/from None instead of erroring.
+    #[serde(
+        rename = "sceneId",
+        default,
+        serialize_with = "serialize_scene_id",
+        deserialize_with = "deserialize_scene_id"
+    )]
+    pub scene: Option<Scenes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimming: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub g: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub b: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub c: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub w: Option<u8>,
+}
+
+fn serialize_scene_id<S>(scene: &Option<Scenes>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match scene {
+        Some(scene) => scene.serialize(serializer),
+        None => 0u32.serialize(serializer),
+    }
+}
+
+fn deserialize_scene_id<'de, D>(deserializer: D) -> Result<Option<Scenes>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let id = u32::deserialize(deserializer)?;
+    if id == 0 {
+        return Ok(None);
+    }
+
+    Scenes::from_id(id)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+impl From<ColorMode> for Pilot {
+    fn from(mode: ColorMode) -> Self {
+        let mut pilot = Pilot::default();
+
+        match mode {
+            ColorMode::Rgb(color) => {
+                pilot.r = Some(color.red);
+                pilot.g = Some(color.green);
+                pilot.b = Some(color.blue);
+            }
+            ColorMode::ColorTemp(temp) => pilot.temp = Some(temp),
+            ColorMode::White(channels) => {
+                pilot.w = Some(channels.warm_white);
+                pilot.c = Some(channels.cool_white);
+            }
+            ColorMode::Scene(scene, speed) => {
+                pilot.scene = Some(scene);
+                pilot.speed = speed;
+            }
+        }
+
+        pilot
+    }
+}
+
+impl DeviceDescriptor {
+    pub fn from_system_config(config: &SystemConfig) -> Self {
+        DeviceDescriptor {
+            module_name: Some(config.module_name.clone()),
+            color_temp: config
+                .ext_range
+                .map(|[min_temp, max_temp]| ColorTempSpace { min_temp, max_temp }),
+            firmware_version: Some(config.firmware_version.clone()),
+            white_channels: None,
+            white_to_color_ratio: None,
+            type_id_index: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pilot_parses_no_scene_as_zero() {
+        let pilot: Pilot =
+            serde_json::from_str(r#"{"state":true,"sceneId":0,"temp":2700,"dimming":100}"#)
+                .unwrap();
+
+        assert_eq!(pilot.scene, None);
+        assert_eq!(pilot.temp, Some(2700));
+    }
+
+    #[test]
+    fn pilot_parses_a_real_scene_id() {
+        let pilot: Pilot = serde_json::from_str(r#"{"sceneId":5,"speed":120}"#).unwrap();
+
+        assert_eq!(pilot.scene, Some(Scenes::Fireplace));
+    }
+}
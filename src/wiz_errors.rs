@@ -0,0 +1,46 @@
+use thiserror::Error;
+
+use super::model::{DeviceDescriptor, Scenes};
+
+#[derive(Error, Debug)]
+#[error("{details}")]
+pub struct SceneIDError {
+    pub given_id: u32,
+    pub details: String,
+}
+
+#[derive(Error, Debug)]
+#[error("{details}")]
+pub struct SceneNameError {
+    pub given_name: String,
+    pub details: String,
+}
+
+#[derive(Error, Debug)]
+#[error("{details}")]
+pub struct SceneSpeedError {
+    pub scene: Scenes,
+    pub given_speed: u8,
+    pub details: String,
+}
+
+#[derive(Error, Debug)]
+#[error("{details}")]
+pub struct DeviceTypeParseError {
+    pub data: DeviceDescriptor,
+    pub details: String,
+}
+
+#[derive(Error, Debug)]
+#[error("{details}")]
+pub struct DeviceColorTempParseError {
+    pub data: DeviceDescriptor,
+    pub details: String,
+}
+
+#[derive(Error, Debug)]
+#[error("{details}")]
+pub struct DeviceColorModeError {
+    pub data: DeviceDescriptor,
+    pub details: String,
+}